@@ -0,0 +1,240 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Pedersen/FROST distributed key generation.
+//!
+//! Unlike [`crate::frost_utils::split_secret::split_secret`], which requires a fully-formed
+//! `SaplingKey` to exist on one machine before it is Shamir-split, this module lets a group of
+//! participants jointly derive Sapling spend authorizing key shares without any single
+//! participant ever holding (or even momentarily constructing) the whole key.
+//!
+//! The ceremony runs in the standard two FROST DKG rounds:
+//!
+//! 1. [`round1`]: each participant samples a random `min_signers - 1` degree polynomial and
+//!    broadcasts a verifiable secret sharing (VSS) commitment to its coefficients, together with
+//!    a proof of knowledge of the constant term.
+//! 2. [`round2`]: each participant privately sends every other participant the evaluation of its
+//!    polynomial at that participant's FROST identifier.
+//! 3. [`finalize`]: each participant verifies the shares it received against the senders'
+//!    commitments, sums them into its own [`KeyPackage`], and derives the group
+//!    [`PublicKeyPackage`] from the broadcast commitments.
+
+use std::collections::HashMap;
+
+use ironfish_frost::frost::{
+    frost::keys::dkg::{part1, part2, part3},
+    keys::{KeyPackage, PublicKeyPackage},
+};
+use ironfish_frost::participant::Identity;
+use rand::{CryptoRng, RngCore};
+
+use crate::errors::IronfishError;
+use crate::frost_utils::identifier::{derive_identifier, IdentifierAssignment};
+
+/// The secret state a participant holds between [`round1`] and [`round2`]. This must never be
+/// serialized or transmitted to another participant.
+pub type Round1SecretPackage = ironfish_frost::frost::frost::keys::dkg::round1::SecretPackage;
+
+/// The package a participant broadcasts to every other participant after [`round1`].
+pub type Round1Package = ironfish_frost::frost::frost::keys::dkg::round1::Package;
+
+/// The secret state a participant holds between [`round2`] and [`finalize`]. This must never be
+/// serialized or transmitted to another participant.
+pub type Round2SecretPackage = ironfish_frost::frost::frost::keys::dkg::round2::SecretPackage;
+
+/// The package a participant sends privately to a single other participant after [`round2`].
+pub type Round2Package = ironfish_frost::frost::frost::keys::dkg::round2::Package;
+
+/// Runs round 1 of the DKG for `identity`: samples a random polynomial of degree
+/// `min_signers - 1`, and returns the secret state to keep locally alongside the package to
+/// broadcast to every other participant.
+///
+/// `identities` is the full participant set for the ceremony (including `identity` itself) and
+/// is validated through [`IdentifierAssignment::derive`] before anything else happens, so that a
+/// collision between two participants' derived identifiers is caught up front rather than
+/// silently merging their shares later in [`round2`]/[`finalize`]. This is the same derivation
+/// `split_secret` uses, so a given participant set is assigned identical identifiers whichever
+/// path is used to produce their key shares.
+pub fn round1<R: RngCore + CryptoRng>(
+    identity: &Identity,
+    identities: &[Identity],
+    min_signers: u16,
+    mut rng: R,
+) -> Result<(Round1SecretPackage, Round1Package), IronfishError> {
+    IdentifierAssignment::derive(identities)?;
+
+    let identifier = derive_identifier(identity)?;
+    let max_signers: u16 = identities.len().try_into()?;
+    let (secret_package, public_package) = part1(identifier, max_signers, min_signers, &mut rng)?;
+    Ok((secret_package, public_package))
+}
+
+/// Runs round 2 of the DKG: given the round 1 packages broadcast by every other participant,
+/// derives the secret evaluation of our polynomial at every other participant's FROST
+/// identifier.
+///
+/// `round1_packages` must contain an entry for every participant in the ceremony other than
+/// `identity` itself.
+pub fn round2(
+    identity: &Identity,
+    round1_secret_package: Round1SecretPackage,
+    round1_packages: &HashMap<Identity, Round1Package>,
+) -> Result<(Round2SecretPackage, HashMap<Identity, Round2Package>), IronfishError> {
+    let frost_round1_packages = to_frost_map(round1_packages, Some(identity))?;
+
+    let (round2_secret_package, round2_packages) =
+        part2(round1_secret_package, &frost_round1_packages)?;
+
+    let identities_by_frost_id = identities_by_frost_id(round1_packages.keys())?;
+    let round2_packages = round2_packages
+        .into_iter()
+        .map(|(frost_id, package)| {
+            let identity = identities_by_frost_id
+                .get(&frost_id)
+                .cloned()
+                .ok_or_else(|| {
+                    IronfishError::InvalidData(
+                        "frost returned an identifier that was not passed as an input"
+                            .to_string(),
+                    )
+                })?;
+            Ok((identity, package))
+        })
+        .collect::<Result<HashMap<_, _>, IronfishError>>()?;
+
+    Ok((round2_secret_package, round2_packages))
+}
+
+/// Finishes the DKG: verifies every received round 2 share against the sender's round 1
+/// commitment, sums the shares into our [`KeyPackage`], and derives the group
+/// [`PublicKeyPackage`] from the broadcast commitments.
+///
+/// `round1_packages` and `round2_packages` must each contain an entry for every participant in
+/// the ceremony other than `identity` itself.
+pub fn finalize(
+    identity: &Identity,
+    round2_secret_package: &Round2SecretPackage,
+    round1_packages: &HashMap<Identity, Round1Package>,
+    round2_packages: &HashMap<Identity, Round2Package>,
+) -> Result<(KeyPackage, PublicKeyPackage), IronfishError> {
+    let frost_round1_packages = to_frost_map(round1_packages, Some(identity))?;
+    let frost_round2_packages = to_frost_map(round2_packages, None)?;
+
+    let (key_package, frost_public_key_package) = part3(
+        round2_secret_package,
+        &frost_round1_packages,
+        &frost_round2_packages,
+    )?;
+
+    let mut identities: Vec<Identity> = round1_packages.keys().cloned().collect();
+    identities.push(identity.clone());
+
+    let public_key_package = PublicKeyPackage::from_frost(frost_public_key_package, identities);
+
+    Ok((key_package, public_key_package))
+}
+
+fn to_frost_map<T: Clone>(
+    packages: &HashMap<Identity, T>,
+    exclude: Option<&Identity>,
+) -> Result<std::collections::BTreeMap<ironfish_frost::frost::frost::Identifier, T>, IronfishError>
+{
+    packages
+        .iter()
+        .filter(|(identity, _)| exclude.map_or(true, |excluded| excluded != *identity))
+        .map(|(identity, package)| Ok((derive_identifier(identity)?, package.clone())))
+        .collect()
+}
+
+fn identities_by_frost_id<'a>(
+    identities: impl Iterator<Item = &'a Identity>,
+) -> Result<HashMap<ironfish_frost::frost::frost::Identifier, Identity>, IronfishError> {
+    identities
+        .map(|identity| Ok((derive_identifier(identity)?, identity.clone())))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::create_multisig_identities;
+    use ironfish_frost::frost::{frost::keys::reconstruct, JubjubBlake2b512};
+
+    #[test]
+    fn test_dkg_round_trip() {
+        let min_signers = 2;
+        let identities = create_multisig_identities(5);
+
+        // Round 1: every participant samples a polynomial and broadcasts a commitment.
+        let mut round1_secrets = HashMap::new();
+        let mut round1_packages_by_sender: HashMap<Identity, Round1Package> = HashMap::new();
+        for identity in &identities {
+            let rng = rand::thread_rng();
+            let (secret, public) = round1(identity, &identities, min_signers, rng).unwrap();
+            round1_secrets.insert(identity.clone(), secret);
+            round1_packages_by_sender.insert(identity.clone(), public);
+        }
+
+        // Round 2: every participant derives the shares to send to every other participant.
+        let mut round2_secrets = HashMap::new();
+        let mut round2_packages_by_sender: HashMap<Identity, HashMap<Identity, Round2Package>> =
+            HashMap::new();
+        for identity in &identities {
+            let others: HashMap<Identity, Round1Package> = round1_packages_by_sender
+                .iter()
+                .filter(|(id, _)| *id != identity)
+                .map(|(id, pkg)| (id.clone(), pkg.clone()))
+                .collect();
+
+            let (secret, packages) =
+                round2(identity, round1_secrets.remove(identity).unwrap(), &others).unwrap();
+            round2_secrets.insert(identity.clone(), secret);
+            round2_packages_by_sender.insert(identity.clone(), packages);
+        }
+
+        // Finalize: every participant collects the shares addressed to it and verifies them.
+        let mut key_packages = HashMap::new();
+        let mut public_key_package = None;
+        for identity in &identities {
+            let round1_received: HashMap<Identity, Round1Package> = round1_packages_by_sender
+                .iter()
+                .filter(|(id, _)| *id != identity)
+                .map(|(id, pkg)| (id.clone(), pkg.clone()))
+                .collect();
+
+            let round2_received: HashMap<Identity, Round2Package> = round2_packages_by_sender
+                .iter()
+                .filter(|(sender, _)| *sender != identity)
+                .map(|(sender, packages)| (sender.clone(), packages[identity].clone()))
+                .collect();
+
+            let (key_package, pubkeys) = finalize(
+                identity,
+                &round2_secrets[identity],
+                &round1_received,
+                &round2_received,
+            )
+            .unwrap();
+
+            key_packages.insert(identity.clone(), key_package);
+            public_key_package = Some(pubkeys);
+        }
+
+        assert_eq!(key_packages.len(), identities.len());
+        assert!(public_key_package.is_some());
+
+        // `min_signers` of the resulting key packages should be enough to reconstruct the key.
+        let key_parts: Vec<_> = key_packages.values().take(min_signers as usize).cloned().collect();
+        reconstruct::<JubjubBlake2b512>(&key_parts).expect("key reconstruction failed");
+    }
+
+    #[test]
+    fn test_round1_rejects_duplicate_identifiers() {
+        let mut identities = create_multisig_identities(3);
+        identities.push(identities[0].clone());
+
+        let result = round1(&identities[0], &identities, 2, rand::thread_rng());
+        assert!(matches!(result, Err(IronfishError::DuplicatedIdentifier)));
+    }
+}
@@ -0,0 +1,116 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Deterministic, collision-free derivation of FROST identifiers from participant `Identity`.
+//!
+//! Building a `frost_id_map` by calling `Identity::to_frost_identifier` on each identity and
+//! collecting the pairs into a `HashMap` silently relies on identifiers never colliding: if two
+//! identities ever did map to the same identifier, the `HashMap` would collapse them and the
+//! dealer (or a DKG ceremony) would produce fewer shares than there are participants, with no
+//! error raised anywhere. This module derives each identifier deterministically from a
+//! domain-separated hash of the identity's bytes, rejects the zero identifier, and reports any
+//! collision as a typed error -- so the same participant set always yields the same, validated
+//! share assignment across `split_secret` and the `dkg` ceremony alike.
+
+use std::collections::HashMap;
+
+use ironfish_frost::frost::{frost::Identifier, keys::IdentifierList};
+use ironfish_frost::participant::Identity;
+
+use crate::errors::IronfishError;
+
+const IDENTIFIER_DOMAIN: &[u8] = b"Ironfish_FROST_Identifier";
+
+/// A validated assignment of FROST identifiers to the participants in a signing group: every
+/// identity is guaranteed to have produced a distinct, non-zero identifier, and the identifiers
+/// are kept in the same order the identities were supplied in so that the assignment can be
+/// reproduced deterministically.
+pub struct IdentifierAssignment {
+    identities_by_identifier: HashMap<Identifier, Identity>,
+    ordered_identifiers: Vec<Identifier>,
+}
+
+impl IdentifierAssignment {
+    /// Derives a FROST identifier for every identity in `identities`, in order, returning
+    /// [`IronfishError::DuplicatedIdentifier`] if any two identities collide on the same
+    /// identifier.
+    pub fn derive(identities: &[Identity]) -> Result<Self, IronfishError> {
+        let mut identities_by_identifier = HashMap::with_capacity(identities.len());
+        let mut ordered_identifiers = Vec::with_capacity(identities.len());
+
+        for identity in identities {
+            let identifier = derive_identifier(identity)?;
+
+            if identities_by_identifier
+                .insert(identifier, identity.clone())
+                .is_some()
+            {
+                return Err(IronfishError::DuplicatedIdentifier);
+            }
+
+            ordered_identifiers.push(identifier);
+        }
+
+        Ok(Self {
+            identities_by_identifier,
+            ordered_identifiers,
+        })
+    }
+
+    /// The identity a given FROST identifier was derived from.
+    pub fn identity(&self, identifier: &Identifier) -> Option<&Identity> {
+        self.identities_by_identifier.get(identifier)
+    }
+
+    /// The derived identifiers, in the order the identities were supplied in, ready to pass to
+    /// FROST's dealer (`split`) or DKG entry points as an `IdentifierList::Custom` so that the
+    /// same participant set always yields the same share assignment.
+    pub fn identifier_list(&self) -> IdentifierList {
+        IdentifierList::Custom(&self.ordered_identifiers)
+    }
+
+    pub fn into_identities_by_identifier(self) -> HashMap<Identifier, Identity> {
+        self.identities_by_identifier
+    }
+}
+
+/// Derives the single FROST identifier for `identity` using the same domain-separated hash that
+/// [`IdentifierAssignment::derive`] uses for a whole group. Exposed so that every caller which
+/// needs to map an `Identity` to its `Identifier` one at a time (e.g. to look up a share that was
+/// dealt using an `IdentifierAssignment`) agrees with the dealer on the same derivation.
+pub(crate) fn derive_identifier(identity: &Identity) -> Result<Identifier, IronfishError> {
+    let mut input = IDENTIFIER_DOMAIN.to_vec();
+    input.extend_from_slice(&identity.serialize());
+
+    // `Identifier::derive` hashes `input` to a scalar and returns an error if that scalar is
+    // zero, so the zero identifier is rejected here rather than returned.
+    let identifier = Identifier::derive(&input)?;
+
+    Ok(identifier)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::create_multisig_identities;
+
+    #[test]
+    fn test_derive_identifiers_are_deterministic() {
+        let identities = create_multisig_identities(10);
+
+        let first = IdentifierAssignment::derive(&identities).unwrap();
+        let second = IdentifierAssignment::derive(&identities).unwrap();
+
+        assert_eq!(first.ordered_identifiers, second.ordered_identifiers);
+    }
+
+    #[test]
+    fn test_derive_identifiers_detects_collisions() {
+        let mut identities = create_multisig_identities(3);
+        identities.push(identities[0].clone());
+
+        let result = IdentifierAssignment::derive(&identities);
+        assert!(matches!(result, Err(IronfishError::DuplicatedIdentifier)));
+    }
+}
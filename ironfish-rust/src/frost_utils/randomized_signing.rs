@@ -0,0 +1,134 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Re-randomized threshold signing for Sapling spend authorization.
+//!
+//! Sapling spend descriptions are not signed with the raw spend authorizing key `ak`; they are
+//! signed with a per-spend re-randomized key `rk = ak + [alpha]·G` so that spends cannot be
+//! linked back to the account that authorized them. The [`KeyPackage`]/[`PublicKeyPackage`]
+//! produced by [`split_secret`](super::split_secret::split_secret) (or by the
+//! [`dkg`](super::dkg) ceremony) are threshold shares of `ak`, not `rk`, so they cannot be used
+//! directly to build a valid Ironfish spend signature. This module folds the randomizer `alpha`
+//! into the FROST signing ceremony so that the aggregated signature verifies under `rk` instead.
+//!
+//! As with [`dkg`](super::dkg), the ceremony is split into per-participant steps so that no
+//! single process ever needs to hold more than one participant's [`KeyPackage`]:
+//!
+//! 1. [`commit`]: each signer samples fresh signing nonces and publishes the corresponding
+//!    commitments. The nonces must be kept secret and never reused across signatures.
+//! 2. The coordinator gathers every signer's commitments into a [`SigningPackage`] with
+//!    [`build_signing_package`] and sends it back out to the signers.
+//! 3. [`sign`]: each signer produces its signature share from its own nonces, [`KeyPackage`], and
+//!    the [`SigningPackage`], folding in the randomizer so the share is valid for `rk` rather
+//!    than `ak`.
+//! 4. [`aggregate`]: the coordinator combines the signature shares into the final signature,
+//!    never seeing a raw signing share or [`KeyPackage`] at any point.
+
+use std::collections::{BTreeMap, HashMap};
+
+use ironfish_frost::frost::{
+    frost::{round1::SigningCommitments, Signature, SigningPackage},
+    keys::{KeyPackage, PublicKeyPackage},
+    round1::{self, SigningNonces},
+    round2::{self, sign_with_randomizer, SignatureShare},
+    Randomizer,
+};
+use ironfish_frost::participant::Identity;
+use rand::{CryptoRng, RngCore};
+
+use crate::errors::IronfishError;
+use crate::frost_utils::identifier::derive_identifier;
+
+/// The randomizer `alpha` and the values derived from it that are needed to run a FROST signing
+/// ceremony whose aggregated signature verifies under the randomized key
+/// `VK' = VK + [alpha]·G` instead of the group's plain verifying key.
+pub struct RandomizedParams(ironfish_frost::frost::RandomizedParams);
+
+impl RandomizedParams {
+    /// Derives the randomized params from a randomizer scalar, e.g. the value derived from a
+    /// spend's value-commitment randomness.
+    pub fn from_randomizer(
+        public_key_package: &PublicKeyPackage,
+        alpha: [u8; 32],
+    ) -> Result<Self, IronfishError> {
+        let randomizer = Randomizer::deserialize(&alpha)?;
+        let params = ironfish_frost::frost::RandomizedParams::from_randomizer(
+            public_key_package.frost_public_key_package(),
+            randomizer,
+        )?;
+        Ok(Self(params))
+    }
+
+    /// The randomized verifying key `VK' = VK + [alpha]·G` that the signature produced with
+    /// these params will verify under.
+    pub fn randomized_verifying_key(&self) -> &ironfish_frost::frost::VerifyingKey {
+        self.0.randomized_verifying_key()
+    }
+}
+
+/// Samples fresh signing nonces for `key_package` and returns the commitments to publish to the
+/// coordinator alongside them. The returned [`SigningNonces`] are secret and must never leave the
+/// signer holding `key_package`.
+pub fn commit<R: RngCore + CryptoRng>(
+    key_package: &KeyPackage,
+    mut rng: R,
+) -> (SigningNonces, SigningCommitments) {
+    round1::commit(key_package.signing_share(), &mut rng)
+}
+
+/// Run by the coordinator: gathers every signer's published commitments into the
+/// [`SigningPackage`] to send back out to the signers for [`sign`].
+pub fn build_signing_package(
+    commitments: &HashMap<Identity, SigningCommitments>,
+    message: &[u8],
+) -> Result<SigningPackage, IronfishError> {
+    let frost_commitments = commitments
+        .iter()
+        .map(|(identity, commitment)| Ok((derive_identifier(identity)?, *commitment)))
+        .collect::<Result<BTreeMap<_, _>, IronfishError>>()?;
+
+    Ok(SigningPackage::new(frost_commitments, message))
+}
+
+/// Run by a signer: produces this signer's signature share from its own `nonces` and
+/// `key_package`, folding in `randomized_params` so the share is valid for the randomized key
+/// `rk` rather than the plain group key `ak`.
+pub fn sign(
+    nonces: &SigningNonces,
+    key_package: &KeyPackage,
+    signing_package: &SigningPackage,
+    randomized_params: &RandomizedParams,
+) -> Result<SignatureShare, IronfishError> {
+    let share = sign_with_randomizer(
+        signing_package,
+        nonces,
+        key_package,
+        randomized_params.0.randomizer(),
+    )?;
+    Ok(share)
+}
+
+/// Run by the coordinator: combines every signer's signature share into the final signature,
+/// which verifies under `randomized_params.randomized_verifying_key()`. The coordinator never
+/// needs to see a raw signing share or [`KeyPackage`] to do this.
+pub fn aggregate(
+    signing_package: &SigningPackage,
+    signature_shares: &HashMap<Identity, SignatureShare>,
+    public_key_package: &PublicKeyPackage,
+    randomized_params: &RandomizedParams,
+) -> Result<Signature, IronfishError> {
+    let frost_shares = signature_shares
+        .iter()
+        .map(|(identity, share)| Ok((derive_identifier(identity)?, *share)))
+        .collect::<Result<BTreeMap<_, _>, IronfishError>>()?;
+
+    let signature = round2::aggregate_with_randomizer(
+        signing_package,
+        &frost_shares,
+        public_key_package.frost_public_key_package(),
+        &randomized_params.0,
+    )?;
+
+    Ok(signature)
+}
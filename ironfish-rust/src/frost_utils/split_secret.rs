@@ -5,9 +5,9 @@
 use ironfish_frost::participant::Identity;
 use ironfish_frost::{
     frost::{
-        frost::keys::split,
-        keys::{IdentifierList, KeyPackage},
-        SigningKey,
+        frost::keys::{reconstruct, split},
+        keys::{KeyPackage, SigningShare, VerifyingShare},
+        JubjubBlake2b512, SigningKey, VerifyingKey,
     },
     keys::PublicKeyPackage,
 };
@@ -15,6 +15,7 @@ use rand::{CryptoRng, RngCore};
 use std::collections::HashMap;
 
 use crate::errors::IronfishError;
+use crate::frost_utils::identifier::{derive_identifier, IdentifierAssignment};
 use crate::SaplingKey;
 
 pub struct SecretShareConfig {
@@ -27,14 +28,9 @@ pub(crate) fn split_secret<R: RngCore + CryptoRng>(
     config: &SecretShareConfig,
     mut rng: R,
 ) -> Result<(HashMap<Identity, KeyPackage>, PublicKeyPackage), IronfishError> {
-    let mut frost_id_map = config
-        .identities
-        .iter()
-        .cloned()
-        .map(|identity| (identity.to_frost_identifier(), identity))
-        .collect::<HashMap<_, _>>();
-    let frost_ids = frost_id_map.keys().cloned().collect::<Vec<_>>();
-    let identifier_list = IdentifierList::Custom(&frost_ids[..]);
+    validate_min_signers(config.min_signers, config.identities.len())?;
+
+    let identifier_assignment = IdentifierAssignment::derive(&config.identities)?;
 
     let secret_key = SigningKey::deserialize(config.spender_key.spend_authorizing_key.to_bytes())?;
     let max_signers: u16 = config.identities.len().try_into()?;
@@ -43,16 +39,19 @@ pub(crate) fn split_secret<R: RngCore + CryptoRng>(
         &secret_key,
         max_signers,
         config.min_signers,
-        identifier_list,
+        identifier_assignment.identifier_list(),
         &mut rng,
     )?;
 
+    let mut frost_id_map = identifier_assignment.into_identities_by_identifier();
     let mut key_packages: HashMap<_, _> = HashMap::new();
+    let mut signing_shares: HashMap<Identity, SigningShare> = HashMap::new();
 
     for (frost_id, secret_share) in shares {
         let identity = frost_id_map
             .remove(&frost_id)
             .expect("frost returned an identifier that was not passed as an input");
+        signing_shares.insert(identity.clone(), *secret_share.signing_share());
         let key_package = KeyPackage::try_from(secret_share.clone())?;
         key_packages.insert(identity, key_package);
     }
@@ -60,14 +59,93 @@ pub(crate) fn split_secret<R: RngCore + CryptoRng>(
     let public_key_package =
         PublicKeyPackage::from_frost(pubkeys, config.identities.iter().cloned());
 
+    verify_secret_shares(&signing_shares, &public_key_package)?;
+
     Ok((key_packages, public_key_package))
 }
 
+fn validate_min_signers(min_signers: u16, num_identities: usize) -> Result<(), IronfishError> {
+    if min_signers < 2 {
+        return Err(IronfishError::InvalidData(
+            "min_signers must be at least 2".to_string(),
+        ));
+    }
+
+    if min_signers as usize > num_identities {
+        return Err(IronfishError::InvalidData(
+            "min_signers cannot exceed the number of participating identities".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Verifies that each `SigningShare` a participant holds actually lies on the polynomial
+/// committed to in `public_key_package`. `split_secret` runs this itself as a post-condition
+/// before returning, but participants who receive a share out-of-band (e.g. over an untrusted
+/// transport) can also call this directly to confirm their share was not corrupted in transit.
+pub fn verify_secret_shares(
+    shares: &HashMap<Identity, SigningShare>,
+    public_key_package: &PublicKeyPackage,
+) -> Result<(), IronfishError> {
+    for (identity, signing_share) in shares {
+        verify_secret_share(identity, signing_share, public_key_package)?;
+    }
+
+    Ok(())
+}
+
+/// Rebuilds the full `SaplingKey` from at least `min_signers` threshold key packages produced by
+/// `split_secret` (or by the `dkg` ceremony), by running Lagrange interpolation over the shares
+/// to recover the spend authorizing scalar. This is the inverse of `split_secret`: an operator
+/// who has collected enough shares out-of-band can use it to migrate or recover an account.
+/// The whole key is held by the machine that runs this function.
+///
+/// If `public_key_package` is provided, the recovered scalar is checked against its verifying
+/// key so that a set of shares that reconstructs to the *wrong* key (e.g. shares collected from
+/// two different signing groups) is rejected rather than silently returning a bogus key.
+pub fn reconstruct_spender_key(
+    key_packages: &[KeyPackage],
+    public_key_package: Option<&PublicKeyPackage>,
+) -> Result<SaplingKey, IronfishError> {
+    let signing_key = reconstruct::<JubjubBlake2b512>(key_packages)?;
+
+    if let Some(public_key_package) = public_key_package {
+        let verifying_key = VerifyingKey::from(&signing_key);
+        if verifying_key != *public_key_package.verifying_key() {
+            return Err(IronfishError::InvalidData(
+                "reconstructed key does not match the group's verifying key".to_string(),
+            ));
+        }
+    }
+
+    SaplingKey::new(signing_key.to_scalar().to_bytes())
+}
+
+fn verify_secret_share(
+    identity: &Identity,
+    signing_share: &SigningShare,
+    public_key_package: &PublicKeyPackage,
+) -> Result<(), IronfishError> {
+    let identifier = derive_identifier(identity)?;
+    let commitment = public_key_package.verifiable_secret_sharing_commitment();
+
+    let expected_verifying_share = commitment.evaluate(identifier)?;
+    let actual_verifying_share = VerifyingShare::from(signing_share);
+
+    if actual_verifying_share != expected_verifying_share {
+        return Err(IronfishError::InvalidData(
+            "secret share does not match the published commitment".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{keys::SaplingKey, test_util::create_multisig_identities};
-    use ironfish_frost::frost::{frost::keys::reconstruct, JubjubBlake2b512};
 
     #[test]
     fn test_split_secret() {
@@ -98,4 +176,191 @@ mod test {
             config.spender_key.spend_authorizing_key.to_bytes()
         );
     }
+
+    #[test]
+    fn test_split_secret_randomized_signature() {
+        let identities = create_multisig_identities(5);
+
+        let rng = rand::thread_rng();
+        let key = SaplingKey::generate_key();
+
+        let config = SecretShareConfig {
+            min_signers: 2,
+            identities,
+            spender_key: key,
+        };
+
+        let (key_packages, public_key_package) = split_secret(&config, rng).unwrap();
+
+        let alpha = [1u8; 32];
+        let randomized_params =
+            super::super::randomized_signing::RandomizedParams::from_randomizer(
+                &public_key_package,
+                alpha,
+            )
+            .expect("failed to derive randomized params");
+
+        // Only the two signers taking part in this signing ceremony are used below; the
+        // coordinator never touches a `KeyPackage`, only commitments and signature shares.
+        let signers: Vec<_> = key_packages.iter().take(2).collect();
+
+        let mut nonces_by_identity = HashMap::new();
+        let mut commitments = HashMap::new();
+        for (identity, key_package) in &signers {
+            let (nonces, commitment) =
+                super::super::randomized_signing::commit(key_package, rand::thread_rng());
+            nonces_by_identity.insert((*identity).clone(), nonces);
+            commitments.insert((*identity).clone(), commitment);
+        }
+
+        let message = b"ironfish spend description";
+        let signing_package =
+            super::super::randomized_signing::build_signing_package(&commitments, message)
+                .expect("failed to build signing package");
+
+        let mut signature_shares = HashMap::new();
+        for (identity, key_package) in &signers {
+            let share = super::super::randomized_signing::sign(
+                &nonces_by_identity[*identity],
+                key_package,
+                &signing_package,
+                &randomized_params,
+            )
+            .expect("signing failed");
+            signature_shares.insert((*identity).clone(), share);
+        }
+
+        let signature = super::super::randomized_signing::aggregate(
+            &signing_package,
+            &signature_shares,
+            &public_key_package,
+            &randomized_params,
+        )
+        .expect("aggregation failed");
+
+        randomized_params
+            .randomized_verifying_key()
+            .verify(message, &signature)
+            .expect("signature does not verify under the randomized key");
+    }
+
+    #[test]
+    fn test_verify_secret_shares() {
+        let identities = create_multisig_identities(10);
+
+        let rng = rand::thread_rng();
+        let key = SaplingKey::generate_key();
+
+        let config = SecretShareConfig {
+            min_signers: 2,
+            identities,
+            spender_key: key,
+        };
+
+        let (key_packages, public_key_package) = split_secret(&config, rng).unwrap();
+
+        let shares: HashMap<_, _> = key_packages
+            .iter()
+            .map(|(identity, key_package)| (identity.clone(), *key_package.signing_share()))
+            .collect();
+
+        verify_secret_shares(&shares, &public_key_package)
+            .expect("freshly dealt shares should verify against the published commitment");
+    }
+
+    #[test]
+    fn test_split_secret_rejects_duplicate_identifiers() {
+        let mut identities = create_multisig_identities(2);
+        identities.push(identities[0].clone());
+
+        let config = SecretShareConfig {
+            min_signers: 2,
+            identities,
+            spender_key: SaplingKey::generate_key(),
+        };
+
+        let result = split_secret(&config, rand::thread_rng());
+        assert!(matches!(result, Err(IronfishError::DuplicatedIdentifier)));
+    }
+
+    #[test]
+    fn test_split_secret_rejects_too_few_min_signers() {
+        let identities = create_multisig_identities(3);
+
+        let config = SecretShareConfig {
+            min_signers: 1,
+            identities,
+            spender_key: SaplingKey::generate_key(),
+        };
+
+        assert!(split_secret(&config, rand::thread_rng()).is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_spender_key() {
+        let identities = create_multisig_identities(10);
+
+        let rng = rand::thread_rng();
+        let key = SaplingKey::generate_key();
+
+        let config = SecretShareConfig {
+            min_signers: 2,
+            identities,
+            spender_key: key,
+        };
+
+        let (key_packages, public_key_package) = split_secret(&config, rng).unwrap();
+
+        let key_parts: Vec<_> = key_packages.values().take(2).cloned().collect();
+
+        let recovered_key = reconstruct_spender_key(&key_parts, Some(&public_key_package))
+            .expect("reconstruction should succeed with enough shares");
+
+        assert_eq!(
+            recovered_key.spend_authorizing_key.to_bytes(),
+            config.spender_key.spend_authorizing_key.to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_spender_key_requires_enough_shares() {
+        let identities = create_multisig_identities(10);
+
+        let rng = rand::thread_rng();
+        let key = SaplingKey::generate_key();
+
+        let config = SecretShareConfig {
+            min_signers: 3,
+            identities,
+            spender_key: key,
+        };
+
+        let (key_packages, _) = split_secret(&config, rng).unwrap();
+
+        let key_parts: Vec<_> = key_packages.values().take(2).cloned().collect();
+
+        assert!(reconstruct_spender_key(&key_parts, None).is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_spender_key_rejects_mismatched_public_key_package() {
+        let config_a = SecretShareConfig {
+            min_signers: 2,
+            identities: create_multisig_identities(10),
+            spender_key: SaplingKey::generate_key(),
+        };
+        let (key_packages_a, _) = split_secret(&config_a, rand::thread_rng()).unwrap();
+
+        let config_b = SecretShareConfig {
+            min_signers: 2,
+            identities: create_multisig_identities(10),
+            spender_key: SaplingKey::generate_key(),
+        };
+        let (_, public_key_package_b) = split_secret(&config_b, rand::thread_rng()).unwrap();
+
+        let key_parts: Vec<_> = key_packages_a.values().take(2).cloned().collect();
+
+        let result = reconstruct_spender_key(&key_parts, Some(&public_key_package_b));
+        assert!(result.is_err());
+    }
 }